@@ -1,4 +1,6 @@
 use anchor_lang::prelude::*; // this brings in everything that Anchor has to offer!
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{self, Mint, MintTo, Token, TokenAccount};
 
 // a program has a program id aka address which we need to set up
 declare_id!("Cge9tQRBsoRMKMty2tF1taxbv8E3QRnBZHXcN2XrTVah"); // we actually don't need to fill this up in Solana Playground, it is done automatically when we deploy the program.
@@ -6,6 +8,32 @@ declare_id!("Cge9tQRBsoRMKMty2tF1taxbv8E3QRnBZHXcN2XrTVah"); // we actually don'
 // this is written to every account on the blockchain by the anchor program, it basically specifies the type of account it is
 pub const ANCHOR_DISCRIMINATOR_SIZE: usize = 8; // when we save things on the blockchain, we'll need 8 bytes + (size of what we're storing)
 
+// seeds for the PDA that acts as mint authority for the favorites badge - a program (not a wallet) has to be
+// able to sign the mint_to CPI, so authority over the mint lives on a PDA we can sign for with seeds + bump.
+pub const MINT_AUTHORITY_SEED: &[u8] = b"mint-authority";
+
+// seeds for the single shared "Favorites badge" mint, so every caller derives and is forced to pass the
+// same mint PDA instead of each supplying their own arbitrary mint.
+pub const FAVORITES_MINT_SEED: &[u8] = b"favorites-mint";
+
+// sizing for the zero-copy ExtendedFavorites account - fixed arrays instead of Vec/String since
+// zero-copy accounts can't hold Borsh's variable-length types.
+pub const MAX_EXTENDED_BIO_LEN: usize = 1024;
+pub const MAX_EXTENDED_HOBBIES: usize = 200;
+pub const MAX_EXTENDED_HOBBY_LEN: usize = 50;
+
+// number of shards backing the global "users who have set favorites" counter. a single shared PDA would
+// force every initialize_favorites transaction to write-lock the same account and serialize against each
+// other; spreading the count across NUM_SHARDS PDAs lets transactions for users in different shards run
+// in parallel.
+pub const NUM_STATS_SHARDS: u8 = 8;
+
+// picks which shard a given user writes to. deterministic from the user's own pubkey so both the client
+// (deriving the PDA to pass in) and the program (validating the seeds) land on the same shard.
+pub fn stats_shard_index(user: &Pubkey) -> u8 {
+    user.to_bytes()[0] % NUM_STATS_SHARDS
+}
+
 // we can convert a rust code with a macro into an anchor program
 
 // upon adding this below line, suddenly the regular rust module becomes a full Solana smart contract!
@@ -14,13 +42,17 @@ pub const ANCHOR_DISCRIMINATOR_SIZE: usize = 8; // when we save things on the bl
 pub mod favorites {
     use super::*; // this brings everything from the root module (or parent module) into scope, which includes the anchor_lang
 
-    // this is the actual instruction handle, the thing that users are going call
-    pub fn set_favorites(
-        context: Context<SetFavorites>,
+    // this is the instruction that creates a user's Favorites PDA for the very first time.
+    // it uses `init` (not `init_if_needed`) so calling it a second time for the same user simply fails instead of
+    // silently wiping out whatever they'd already saved - creation and mutation are two different instructions now.
+    pub fn initialize_favorites(
+        context: Context<InitializeFavorites>,
         number: u64,
         color: String,
         hobbies: Vec<String>,
     ) -> Result<()> {
+        validate_favorites_input(&color, &hobbies)?;
+
         msg!("Greetings from {}", context.program_id); // messages are basically like console.logs and it writes to the solana log file which could actually be seen when someone makes a transaction calling this instruction
 
         let user_public_key = context.accounts.user.key();
@@ -39,8 +71,214 @@ pub mod favorites {
             hobbies,
         });
 
+        // mint the user a single commemorative "Favorites badge" token now that their PDA exists for the first time.
+        // the mint's authority is the mint_authority PDA, so the program signs the CPI itself using its seeds + bump
+        // instead of needing a wallet's private key.
+        let bump = context.bumps.mint_authority;
+        let signer_seeds: &[&[u8]] = &[MINT_AUTHORITY_SEED, &[bump]];
+
+        token::mint_to(
+            CpiContext::new_with_signer(
+                context.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: context.accounts.favorites_mint.to_account_info(),
+                    to: context.accounts.user_token_account.to_account_info(),
+                    authority: context.accounts.mint_authority.to_account_info(),
+                },
+                &[signer_seeds],
+            ),
+            1,
+        )?;
+
+        // bump this user's shard of the global "favorites set" counter instead of a single shared account,
+        // so users landing in different shards don't contend with each other.
+        context.accounts.stats_shard.count += 1;
+
+        // structured event for off-chain indexers, instead of making them parse msg! log lines.
+        emit!(FavoritesUpdated {
+            user: user_public_key,
+            number,
+            color: context.accounts.favorites.color.clone(),
+        });
+
         Ok(())
     }
+
+    // read-only helper that totals the global counter across every shard. the client passes all
+    // NUM_STATS_SHARDS shard PDAs in as remaining_accounts since there's no single fixed-size set of
+    // accounts that covers "all the shards" ahead of time - so unlike a normal #[account(seeds = ...)]
+    // constraint, we have to re-derive and check each shard's canonical PDA ourselves. for every shard index
+    // we require exactly one remaining account at its canonical address, which also rules out the same
+    // shard being passed twice (it could only ever satisfy one index) or a shard being left out entirely.
+    pub fn get_favorites_stats_total(context: Context<GetFavoritesStatsTotal>) -> Result<u64> {
+        require_eq!(
+            context.remaining_accounts.len(),
+            NUM_STATS_SHARDS as usize,
+            FavoritesError::WrongStatsShardCount
+        );
+
+        let mut total: u64 = 0;
+
+        for shard_index in 0..NUM_STATS_SHARDS {
+            let (expected_address, _bump) =
+                Pubkey::find_program_address(&[b"stats", &[shard_index]], &crate::ID);
+
+            let shard_info = context
+                .remaining_accounts
+                .iter()
+                .find(|account_info| account_info.key() == expected_address)
+                .ok_or(FavoritesError::MissingStatsShard)?;
+
+            // a shard that no user has landed on yet was never created by init_if_needed, so it's still
+            // owned by the System Program with no data - that's not an error, it just contributes zero.
+            // the caller still has to pass its canonical address though, so shards can't be silently omitted.
+            let shard_count = if shard_info.owner == &System::id() {
+                0
+            } else {
+                let shard: Account<StatsShard> = Account::try_from(shard_info)?;
+                shard.count
+            };
+
+            total = total
+                .checked_add(shard_count)
+                .ok_or(FavoritesError::StatsOverflow)?;
+        }
+
+        msg!("Total users who have set favorites: {}", total);
+
+        Ok(total)
+    }
+
+    // this instruction is for users who already have a Favorites PDA and just want to change what's in it.
+    // since the account already exists, we only need `mut` here - no `payer`/`system_program`, there's nothing to create.
+    pub fn update_favorites(
+        context: Context<UpdateFavorites>,
+        number: u64,
+        color: String,
+        hobbies: Vec<String>,
+    ) -> Result<()> {
+        validate_favorites_input(&color, &hobbies)?;
+
+        let user_public_key = context.accounts.user.key();
+
+        msg!(
+            "User {} is updating their favorite number to {}, favorite color to {}",
+            user_public_key,
+            number,
+            color
+        );
+        msg!("User's hobbies are now: {:?}", hobbies);
+
+        context.accounts.favorites.set_inner(Favorites {
+            number,
+            color,
+            hobbies,
+        });
+
+        emit!(FavoritesUpdated {
+            user: user_public_key,
+            number,
+            color: context.accounts.favorites.color.clone(),
+        });
+
+        Ok(())
+    }
+
+    // lets a user close their own Favorites PDA and get the rent lamports they originally paid back.
+    // all the actual work happens in the `close = user` constraint on the CloseFavorites accounts struct below.
+    pub fn close_favorites(context: Context<CloseFavorites>) -> Result<()> {
+        msg!(
+            "Closing favorites account for user {}",
+            context.accounts.user.key()
+        );
+
+        Ok(())
+    }
+
+    // opt-in variant of initialize_favorites for users whose data is too big for a plain `Account<Favorites>`.
+    // `ExtendedFavorites` is zero_copy, so Anchor hands us a reference straight into the account's backing
+    // buffer via `AccountLoader` instead of deserializing the whole thing onto the stack.
+    pub fn initialize_extended_favorites(
+        context: Context<InitializeExtendedFavorites>,
+        number: u64,
+    ) -> Result<()> {
+        let mut extended_favorites = context.accounts.extended_favorites.load_init()?;
+        extended_favorites.number = number;
+        extended_favorites.bio_len = 0;
+        extended_favorites.hobby_count = 0;
+
+        Ok(())
+    }
+
+    // appends one hobby to an existing ExtendedFavorites account, mutating it in place via `load_mut()`
+    // rather than reading the whole struct out, modifying it, and writing it back with `set_inner`.
+    pub fn add_extended_hobby(
+        context: Context<UpdateExtendedFavorites>,
+        hobby: String,
+    ) -> Result<()> {
+        require_gte!(MAX_EXTENDED_HOBBY_LEN, hobby.len(), FavoritesError::HobbyTooLong);
+
+        let mut extended_favorites = context.accounts.extended_favorites.load_mut()?;
+        let index = extended_favorites.hobby_count as usize;
+        require_gte!(
+            MAX_EXTENDED_HOBBIES,
+            index + 1,
+            FavoritesError::TooManyExtendedHobbies
+        );
+
+        let mut padded = [0u8; MAX_EXTENDED_HOBBY_LEN];
+        padded[..hobby.len()].copy_from_slice(hobby.as_bytes());
+        extended_favorites.hobbies[index] = padded;
+        extended_favorites.hobby_lens[index] = hobby.len() as u8;
+        extended_favorites.hobby_count += 1;
+
+        Ok(())
+    }
+}
+
+// shared by initialize_favorites and update_favorites so both instructions reject the same bad input
+// the same way, instead of letting an oversized color/hobby silently get truncated (or panic) during serialization.
+fn validate_favorites_input(color: &str, hobbies: &[String]) -> Result<()> {
+    require!(!color.is_empty(), FavoritesError::EmptyColor);
+    require_gte!(50, color.len(), FavoritesError::ColorTooLong);
+    require_gte!(5, hobbies.len(), FavoritesError::TooManyHobbies);
+
+    for hobby in hobbies {
+        require_gte!(50, hobby.len(), FavoritesError::HobbyTooLong);
+    }
+
+    Ok(())
+}
+
+// emitted whenever a user's favorites are set or changed, so off-chain indexers/frontends can subscribe to
+// and deserialize state changes instead of scraping msg! log lines.
+#[event]
+pub struct FavoritesUpdated {
+    pub user: Pubkey,
+    pub number: u64,
+    pub color: String,
+}
+
+// typed errors for bad favorites input - these show up in the generated IDL and in clients' logs
+// instead of a generic "failed to serialize" panic.
+#[error_code]
+pub enum FavoritesError {
+    #[msg("Color must not be empty")]
+    EmptyColor,
+    #[msg("Color must be at most 50 bytes")]
+    ColorTooLong,
+    #[msg("At most 5 hobbies are allowed")]
+    TooManyHobbies,
+    #[msg("Each hobby must be at most 50 bytes")]
+    HobbyTooLong,
+    #[msg("Stats shard counter overflowed")]
+    StatsOverflow,
+    #[msg("Expected exactly NUM_STATS_SHARDS remaining accounts")]
+    WrongStatsShardCount,
+    #[msg("A stats shard's canonical PDA was not among the supplied accounts")]
+    MissingStatsShard,
+    #[msg("At most 200 hobbies are allowed on an extended favorites account")]
+    TooManyExtendedHobbies,
 }
 
 // this account is what we're gonna write onto the blockchain for every user
@@ -60,14 +298,45 @@ pub struct Favorites {
     pub hobbies: Vec<String>,
 }
 
-// when people call our set Favorites function, they're gonna need to specify the accounts they're gonna modify on blockchain
+// opt-in account for users whose favorites don't fit Favorites' small limits - e.g. a long bio or hundreds
+// of hobbies. `zero_copy` means Anchor never deserializes this onto the stack; instead `AccountLoader` gives
+// us a direct reference into the account's backing buffer, so the size here isn't bounded by the stack or
+// the 10MB `Box` limit the way a plain `Account<T>` would be.
+//
+// fields are ordered by descending alignment (u64, then u32, then u16, then the byte arrays) to minimize
+// padding between them, and the large variable-length-in-spirit collections (`bio`, `hobbies`) are still
+// pushed to the end, per Anchor's zero-copy layout guidance. `#[account(zero_copy)]` makes this `#[repr(C)]`
+// with the struct's own alignment (8, from `number`) and derives `bytemuck::Pod`, which refuses any implicit
+// padding. the data fields add up to 11238 bytes, 2 short of a multiple of 8, so those 2 bytes are declared
+// explicitly as `_padding` after `hobbies`. this account is sized with `size_of::<Self>()` at its `init`
+// site (see InitializeExtendedFavorites).
+#[account(zero_copy)]
+pub struct ExtendedFavorites {
+    pub number: u64,
+    pub hobby_count: u32,
+    pub bio_len: u16,
+    pub bio: [u8; MAX_EXTENDED_BIO_LEN],
+    pub hobby_lens: [u8; MAX_EXTENDED_HOBBIES],
+    pub hobbies: [[u8; MAX_EXTENDED_HOBBY_LEN]; MAX_EXTENDED_HOBBIES],
+    pub _padding: [u8; 2],
+}
+
+// one shard of the global "users who have set favorites" counter. there are NUM_STATS_SHARDS of these PDAs;
+// see stats_shard_index for how a user is routed to one.
+#[account]
+#[derive(InitSpace)]
+pub struct StatsShard {
+    pub count: u64,
+}
+
+// when people call our initialize favorites function, they're gonna need to specify the accounts they're gonna modify on blockchain
 // solana isn't single threaded, it can process multiple things at the same time
 // to make this possible
 
-// this struct basically defines the set of accounts required to interact with our program (specifically for set_favorites function defined earlier)
+// this struct basically defines the set of accounts required to interact with our program (specifically for initialize_favorites function defined earlier)
 
 #[derive(Accounts)] // this lets anchor know that
-pub struct SetFavorites<'info> {
+pub struct InitializeFavorites<'info> {
     // this means the user account can be mutated (changed) during the transaction.
     // It allows you to modify the account's state, like decreasing their balance to pay for transaction fees or making other modifications.
     // In this case, it allows the user's account to pay for rent/fees.
@@ -75,30 +344,291 @@ pub struct SetFavorites<'info> {
     pub user: Signer<'info>,
 
     #[account(
-        init_if_needed, // create the account if it doesn't exist.
+        init, // create the account - fails if it already exists, so we can never clobber an existing user's data
         payer = user, // set the payer to user for making the account
         space = ANCHOR_DISCRIMINATOR_SIZE + Favorites::INIT_SPACE, // we'll can use INIT_SPACE to get the size of the favorites struct
         seeds = [b"favorites", user.key().as_ref()], // since this account is a PDA, we'll need some seeds to derive the address for the account
         bump // this is just used to find the correct PDA
 
         // we need to pass the public key as a reference (as a slice of the PubKey) as the seeds expect a slice and not directly an PubKey object.
-        // In Rust, the b prefix before a string literal (like "favorites") means that the string is interpreted as a byte string (&[u8]). 
+        // In Rust, the b prefix before a string literal (like "favorites") means that the string is interpreted as a byte string (&[u8]).
         // In other words, it converts the string into a byte array of type &[u8] rather than a regular string slice (&str).
         // for example: b"favorites"  is equivalent to &[102, 97, 118, 111, 114, 105, 116, 101, 115]
     )]
     pub favorites: Account<'info, Favorites>,
 
+    // the mint for the "Favorites badge" token, created once and shared by every user. it's a PDA under
+    // FAVORITES_MINT_SEED (not an arbitrary caller-supplied keypair), so every caller is forced to derive and
+    // pass this exact same account - that's what actually makes it "the" shared mint instead of each caller
+    // getting their own. authority is the mint_authority PDA below, so only this program can ever mint new badges.
+    #[account(
+        init_if_needed, // the very first user to initialize their favorites creates the shared mint, everyone after that just reuses it
+        payer = user,
+        mint::decimals = 0, // badges aren't divisible, you either have one or you don't
+        mint::authority = mint_authority,
+        seeds = [FAVORITES_MINT_SEED],
+        bump,
+    )]
+    pub favorites_mint: Account<'info, Mint>,
+
+    // a PDA that exists purely to hold mint authority so the program can sign the mint_to CPI itself,
+    // no wallet keypair involved.
+    #[account(
+        seeds = [MINT_AUTHORITY_SEED],
+        bump,
+    )]
+    pub mint_authority: SystemAccount<'info>,
+
+    // the user's associated token account for the badge mint, created on demand if this is their first badge.
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = favorites_mint,
+        associated_token::authority = user,
+    )]
+    pub user_token_account: Account<'info, TokenAccount>,
+
+    // this user's shard of the global favorites-set counter. seeds are derived from stats_shard_index(user),
+    // so different users are spread across different shards and don't contend on the same account.
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = ANCHOR_DISCRIMINATOR_SIZE + StatsShard::INIT_SPACE,
+        seeds = [b"stats", &[stats_shard_index(&user.key())]],
+        bump
+    )]
+    pub stats_shard: Account<'info, StatsShard>,
+
     // the System Program is needed as it is responsible for basic operations on the blockchain, such as creating new accounts or transferring SOL.
     pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+// no fixed accounts needed here - the caller passes every StatsShard PDA to sum as remaining_accounts.
+#[derive(Accounts)]
+pub struct GetFavoritesStatsTotal {}
+
+// this struct defines the accounts needed to update an *existing* Favorites PDA.
+// there's no payer and no system_program here - we're not creating anything, just writing to an account that's already there.
+#[derive(Accounts)]
+pub struct UpdateFavorites<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut, // the account already exists, we're just mutating its data
+        seeds = [b"favorites", user.key().as_ref()],
+        bump
+    )]
+    pub favorites: Account<'info, Favorites>,
+}
+
+// this struct defines the accounts needed to close a user's Favorites PDA and refund its rent.
+#[derive(Accounts)]
+pub struct CloseFavorites<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"favorites", user.key().as_ref()],
+        bump,
+        close = user // sends this account's rent lamports back to `user` and zeroes out its data
+    )]
+    pub favorites: Account<'info, Favorites>,
+}
+
+// accounts needed to create a user's zero-copy ExtendedFavorites PDA. space is `size_of::<ExtendedFavorites>()`
+// - `AccountLoader` reinterprets the account's raw bytes as the `#[repr(C)]` struct itself, so the allocation
+// has to match the type's real in-memory size, the explicit `_padding` bytes included.
+#[derive(Accounts)]
+pub struct InitializeExtendedFavorites<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(
+        init,
+        payer = user,
+        space = ANCHOR_DISCRIMINATOR_SIZE + std::mem::size_of::<ExtendedFavorites>(),
+        seeds = [b"extended-favorites", user.key().as_ref()],
+        bump
+    )]
+    pub extended_favorites: AccountLoader<'info, ExtendedFavorites>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// accounts needed to mutate an existing ExtendedFavorites PDA - just like UpdateFavorites, no payer or
+// system_program since nothing is being created.
+#[derive(Accounts)]
+pub struct UpdateExtendedFavorites<'info> {
+    pub user: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"extended-favorites", user.key().as_ref()],
+        bump
+    )]
+    pub extended_favorites: AccountLoader<'info, ExtendedFavorites>,
 }
 
 // In Solana, every transaction must declare the accounts it will read from or write to in advance.
 // This is a security and performance feature, ensuring that the transaction doesn't modify unexpected parts of the blockchain.
-// The SetFavorites<'info> struct defines which accounts are needed when the set_favorites function is called.
 
 // Something great that is enforced by default is that the person signing the program has to be writing to their own favorites account
 // because we have set seeds = [b"favorites", user.key().as_ref()] which includes the signing user's public key
 
 // Txn ID for deploying the program: 3hrTZrjjJN9db3xjGU7H1iLQCwm1WaohtFLDZY1e17bKHgHwpqTnuvc6VLqqxiMd2zCTYHQVBH47M6adEyZSm6G5
 // Upgradation ID: AnSaS9vQNkVGDcpmAhwMyaK1VhC1TTik2WvogsqpXKAee68iFMgzFF5nqn9eTeDoGmeovjdGZxQ2ircC6okHN72
-// Test Txn ID: 5mkrAYhTzbNVCqxXad2tqbhvN4SwoDNautgv9faZfuThGBUWUNUv1nY66tPBNnFFP8Fy6cdHpHaqusWCtSZYMAgP
\ No newline at end of file
+// Test Txn ID: 5mkrAYhTzbNVCqxXad2tqbhvN4SwoDNautgv9faZfuThGBUWUNUv1nY66tPBNnFFP8Fy6cdHpHaqusWCtSZYMAgP
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::cell::Cell;
+
+    // tracks bytes allocated by the *current thread* only, so the benchmark below isn't polluted by other
+    // tests' allocations running concurrently on other threads (cargo test's default runner gives each test
+    // its own OS thread). only active for `cargo test` - the on-chain program build never compiles this
+    // module in, so it can't affect the BPF allocator.
+    thread_local! {
+        static THREAD_BYTES_ALLOCATED: Cell<usize> = Cell::new(0);
+    }
+
+    struct CountingAllocator;
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            THREAD_BYTES_ALLOCATED.with(|bytes| bytes.set(bytes.get() + layout.size()));
+            System.alloc(layout)
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout)
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    fn bytes_allocated_during<T>(f: impl FnOnce() -> T) -> (T, usize) {
+        let before = THREAD_BYTES_ALLOCATED.with(|bytes| bytes.get());
+        let result = f();
+        let after = THREAD_BYTES_ALLOCATED.with(|bytes| bytes.get());
+        (result, after - before)
+    }
+
+    // builds a fake account whose data is the exact layout `init` with
+    // `space = ANCHOR_DISCRIMINATOR_SIZE + size_of::<ExtendedFavorites>()` produces: the 8-byte Anchor
+    // discriminator followed by `size_of::<ExtendedFavorites>()` zeroed bytes.
+    fn zeroed_extended_favorites_account_data() -> Vec<u8> {
+        let mut data = vec![0u8; ANCHOR_DISCRIMINATOR_SIZE + std::mem::size_of::<ExtendedFavorites>()];
+        data[..ANCHOR_DISCRIMINATOR_SIZE].copy_from_slice(&ExtendedFavorites::DISCRIMINATOR);
+        data
+    }
+
+    // `Account<Favorites>` deserializes by calling `Favorites::try_from_slice`, which has to allocate heap
+    // memory to own its `String`/`Vec` fields - every read copies the account's bytes. `AccountLoader` over a
+    // zero_copy account skips that step: it reinterprets the account's own backing buffer in place through the
+    // real `AccountLoader::try_from`/`load` path (not a raw `bytemuck` cast), using account data sized exactly
+    // the way `InitializeExtendedFavorites` sizes it, so this should allocate nothing at all.
+    #[test]
+    fn zero_copy_read_avoids_the_allocation_a_plain_account_deserialize_requires() {
+        let favorites = Favorites {
+            number: 7,
+            color: "a".repeat(50),
+            hobbies: vec!["b".repeat(50); 5],
+        };
+        let favorites_bytes = favorites.try_to_vec().unwrap();
+
+        let (_, plain_account_allocated_bytes) =
+            bytes_allocated_during(|| Favorites::try_from_slice(&favorites_bytes[..]).unwrap());
+        assert!(
+            plain_account_allocated_bytes > 0,
+            "expected deserializing a plain Account<Favorites> to allocate heap memory for its owned String/Vec fields"
+        );
+
+        let key = Pubkey::new_unique();
+        let owner = crate::ID;
+        let mut lamports = 0u64;
+        let mut data = zeroed_extended_favorites_account_data();
+        let account_info =
+            AccountInfo::new(&key, false, true, &mut lamports, &mut data, &owner, false, 0);
+
+        let (read_number, zero_copy_allocated_bytes) = bytes_allocated_during(|| {
+            let loader = AccountLoader::<ExtendedFavorites>::try_from(&account_info).unwrap();
+            loader.load().unwrap().number
+        });
+
+        assert_eq!(read_number, 0);
+        assert_eq!(
+            zero_copy_allocated_bytes, 0,
+            "expected reading a much bigger ExtendedFavorites through AccountLoader to allocate nothing"
+        );
+    }
+
+    // ExtendedFavorites' data fields add up to 11238 bytes, 2 short of `size_of::<ExtendedFavorites>()`
+    // (11240, the explicit `_padding` included). an account sized off the data fields alone - the old
+    // INIT_SPACE-based way - is 2 bytes short of what `AccountLoader` needs, and this test confirms it is
+    // rejected, either by `AccountLoader::try_from` or, since `try_from` only checks the owner and the
+    // discriminator, by `load()`. that an account sized with `size_of::<ExtendedFavorites>()`, the way
+    // `InitializeExtendedFavorites` sizes it, is accepted is covered by the zero-copy read test above.
+    #[test]
+    fn account_loader_rejects_an_account_sized_the_old_init_space_way() {
+        let key = Pubkey::new_unique();
+        let owner = crate::ID;
+
+        let mut undersized_lamports = 0u64;
+        let mut undersized_data = zeroed_extended_favorites_account_data();
+        undersized_data.truncate(undersized_data.len() - 2); // the old INIT_SPACE-based under-count
+        let undersized_account_info = AccountInfo::new(
+            &key,
+            false,
+            true,
+            &mut undersized_lamports,
+            &mut undersized_data,
+            &owner,
+            false,
+            0,
+        );
+        let rejected = match AccountLoader::<ExtendedFavorites>::try_from(&undersized_account_info) {
+            Err(_) => true,
+            Ok(loader) => !matches!(
+                std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| loader.load().map(|_| ()))),
+                Ok(Ok(()))
+            ),
+        };
+        assert!(
+            rejected,
+            "expected an account 2 bytes short of size_of::<ExtendedFavorites>() to be rejected"
+        );
+    }
+
+    // NOTE: this only covers FavoritesUpdated's serialization framing, not the real `emit!` call sites in
+    // initialize_favorites/update_favorites - there's no solana-program-test/BanksClient harness in this repo
+    // to actually submit a transaction and read back its logs, so a broken or removed `emit!` would not be
+    // caught here. `emit!` logs events as their 8-byte discriminator followed by the Borsh-serialized struct;
+    // an indexer watching transaction logs strips that discriminator and deserializes the rest back into the
+    // event, which is the round trip this test exercises directly on a hand-built FavoritesUpdated.
+    #[test]
+    fn favorites_updated_event_discriminator_framing_round_trips() {
+        let event = FavoritesUpdated {
+            user: Pubkey::new_unique(),
+            number: 42,
+            color: "blue".to_string(),
+        };
+
+        let mut logged_bytes = FavoritesUpdated::DISCRIMINATOR.to_vec();
+        logged_bytes.extend(event.try_to_vec().unwrap());
+
+        let (discriminator, data) = logged_bytes.split_at(8);
+        assert_eq!(discriminator, FavoritesUpdated::DISCRIMINATOR);
+
+        let parsed_event = FavoritesUpdated::try_from_slice(data).unwrap();
+
+        assert_eq!(parsed_event.user, event.user);
+        assert_eq!(parsed_event.number, event.number);
+        assert_eq!(parsed_event.color, event.color);
+    }
+}